@@ -1,8 +1,10 @@
-use clap::Parser;
+mod manifest;
+mod sink;
+
+use clap::{Parser, ValueEnum};
 use std::path::{Path, PathBuf};
 use tokio::fs;
 use tokio::time::Instant;
-use tokio::io::AsyncWriteExt;
 use anyhow::{Context, Result};
 use tracing::{info, warn, error};
 use reqwest::{Client, ClientBuilder};
@@ -11,6 +13,10 @@ use serde_json::Value;
 use futures::stream::{self, StreamExt};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+use indicatif::{ProgressBar, ProgressStyle};
+use manifest::{EmojiStatus, Manifest};
+use serde::Serialize;
+use sink::{DirSink, EmojiSink, ZipSink};
 
 #[derive(Parser)]
 #[command(name = "hackclub-slack-emoji-dl")]
@@ -30,6 +36,83 @@ struct Args {
 
 	#[arg(long, default_value = "https://badger.hackclub.dev/api/emoji")]
 	api_url: String,
+
+	#[arg(long, help = "Link alias emoji to their target's downloaded file instead of downloading a separate copy")]
+	link_aliases: bool,
+
+	#[arg(short = 'H', long = "header", help = "Additional HTTP header to send with every request, as \"Key: Value\" (repeatable)")]
+	headers: Vec<String>,
+
+	#[arg(long, help = "Shortcut for --header \"Authorization: Bearer <token>\"")]
+	token: Option<String>,
+
+	#[arg(long, default_value = "5", help = "Maximum number of redirects to follow before giving up")]
+	max_redirects: usize,
+
+	#[arg(long, value_enum, default_value_t = OutputFormat::Dir, help = "Write emojis to a directory or into a single zip archive")]
+	format: OutputFormat,
+
+	#[arg(long, default_value = "3", help = "Number of attempts before giving up on an emoji")]
+	max_retries: usize,
+
+	#[arg(long, default_value = "500", help = "Base delay before the first retry, in ms (doubles each subsequent attempt)")]
+	retry_base_delay_ms: u64,
+
+	#[arg(long, default_value = "30000", help = "Upper bound on the retry backoff delay, in ms")]
+	fail_wait_ms: u64,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+	Dir,
+	Zip,
+}
+
+/// Builds the `HeaderMap` sent with every request from `--header "Key: Value"`
+/// entries and the `--token` shortcut.
+fn build_headers(args: &Args) -> Result<reqwest::header::HeaderMap> {
+	use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
+
+	let mut headers = HeaderMap::new();
+
+	for header in &args.headers {
+		let (key, value) = header
+			.split_once(':')
+			.context(format!("Invalid --header {:?}, expected \"Key: Value\"", header))?;
+
+		let name = HeaderName::from_bytes(key.trim().as_bytes())
+			.context(format!("Invalid header name in {:?}", header))?;
+		let value = HeaderValue::from_str(value.trim())
+			.context(format!("Invalid header value in {:?}", header))?;
+
+		headers.insert(name, value);
+	}
+
+	if let Some(token) = &args.token {
+		let value = HeaderValue::from_str(&format!("Bearer {}", token))
+			.context("Invalid --token value")?;
+		headers.insert(AUTHORIZATION, value);
+	}
+
+	Ok(headers)
+}
+
+/// A redirect policy that follows at most `max_redirects` hops and stops
+/// before crossing to a different host, so `--header`/`--token` credentials
+/// for the original host are never replayed against a redirect target.
+fn same_host_redirect_policy(max_redirects: usize) -> reqwest::redirect::Policy {
+	reqwest::redirect::Policy::custom(move |attempt| {
+		if attempt.previous().len() > max_redirects {
+			return attempt.error("too many redirects");
+		}
+
+		let original_host = attempt.previous().first().and_then(|u| u.host_str());
+		if attempt.url().host_str() != original_host {
+			return attempt.stop();
+		}
+
+		attempt.follow()
+	})
 }
 
 fn sanitise_filename(name: &str) -> String {
@@ -40,46 +123,160 @@ fn sanitise_filename(name: &str) -> String {
 		.to_string()
 }
 
-fn extract_extension(url: &str) -> String {
-	Path::new(url)
+const KNOWN_IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp", "svg"];
+
+/// Pulls a reliable image extension out of the URL path, ignoring any query
+/// string. Returns `None` when the path has no extension or an unrecognised
+/// one, since CDN URLs often omit it or carry something unrelated.
+fn extract_extension(url: &str) -> Option<String> {
+	let path = url.split(['?', '#']).next().unwrap_or(url);
+	Path::new(path)
 		.extension()
 		.and_then(|ext| ext.to_str())
-		.map(|ext| if ext.starts_with('.') { ext.to_string() } else { format!(".{}", ext) })
-		.unwrap_or_else(|| ".png".to_string())
+		.map(|ext| ext.to_lowercase())
+		.filter(|ext| KNOWN_IMAGE_EXTENSIONS.contains(&ext.as_str()))
+		.map(|ext| format!(".{}", ext))
 }
 
-async fn download_emoji(
-	client: &Client,
-	name: String,
-	url: String,
-	output_dir: &Path,
+/// Maps an HTTP `Content-Type` header value to a file extension, for URLs
+/// that don't carry a reliable extension of their own.
+fn extension_from_content_type(content_type: &str) -> Option<String> {
+	let mime = content_type.split(';').next().unwrap_or(content_type).trim();
+	match mime {
+		"image/gif" => Some(".gif".to_string()),
+		"image/webp" => Some(".webp".to_string()),
+		"image/jpeg" => Some(".jpg".to_string()),
+		"image/png" => Some(".png".to_string()),
+		"image/svg+xml" => Some(".svg".to_string()),
+		_ => None,
+	}
+}
+
+const ALIAS_PREFIX: &str = "alias:";
+
+/// Follows an `alias:<target>` chain to the emoji it ultimately points at,
+/// returning that emoji's name and URL. Returns `None` for a cycle (a name
+/// reached a second time along the chain) or a dangling alias (a target that
+/// is neither a URL entry nor another alias).
+fn resolve_alias<'a>(
+	start: &str,
+	urls: &'a HashMap<String, String>,
+	aliases: &HashMap<String, String>,
+) -> Option<(&'a str, &'a str)> {
+	let mut current = start;
+	let mut visited = HashSet::new();
+
+	loop {
+		if !visited.insert(current.to_string()) {
+			warn!("Alias cycle detected starting at {}", start);
+			return None;
+		}
+
+		if let Some((name, url)) = urls.get_key_value(current) {
+			return Some((name.as_str(), url.as_str()));
+		}
+
+		match aliases.get(current) {
+			Some(target) => current = target,
+			None => {
+				warn!("Alias {} points at unknown emoji {}", start, current);
+				return None;
+			}
+		}
+	}
+}
+
+/// Points `alias_name` at the already-downloaded file for `target_name` by
+/// symlinking (falling back to a copy on platforms without symlink support),
+/// so alias emoji don't need a redundant download of the same bytes. Only
+/// meaningful for `DirSink`'s plain-directory output; a `.zip` has no
+/// separate filesystem entries to point at each other, so `--link-aliases`
+/// is ignored for `--format zip`.
+async fn link_alias(output_dir: &Path, alias_name: &str, target_name: &str) -> Result<()> {
+	let sanitised_alias = sanitise_filename(alias_name);
+	let sanitised_target = sanitise_filename(target_name);
+
+	let target_path = sink::find_by_stem(output_dir, &sanitised_target)
+		.await
+		.context(format!("No downloaded file found for alias target {}", target_name))?;
+
+	let extension = target_path.extension().and_then(|e| e.to_str()).unwrap_or("png");
+	let link_path = output_dir.join(format!("{}.{}", sanitised_alias, extension));
+
+	if link_path.exists() {
+		return Ok(());
+	}
+
+	#[cfg(unix)]
+	{
+		// A relative symlink target resolves against the link's own directory,
+		// and both files live in output_dir, so link to the bare filename.
+		let link_target = target_path.file_name().map(PathBuf::from).unwrap_or_else(|| target_path.clone());
+		std::os::unix::fs::symlink(&link_target, &link_path)
+			.context(format!("Failed to symlink {} -> {}", link_path.display(), target_path.display()))?;
+	}
+
+	#[cfg(not(unix))]
+	{
+		fs::copy(&target_path, &link_path)
+			.await
+			.context(format!("Failed to copy {} -> {}", target_path.display(), link_path.display()))?;
+	}
+
+	Ok(())
+}
+
+/// Shared state threaded through every `download_emoji`/`download_emoji_with_retry`
+/// call in a batch, bundled up so adding a new piece of shared state doesn't
+/// mean adding another function argument everywhere.
+#[derive(Clone)]
+struct DownloadContext {
+	client: Client,
+	sink: Arc<dyn EmojiSink>,
+	manifest: Arc<Manifest>,
 	completed: Arc<AtomicUsize>,
 	total: usize,
 	skip_existence_check: bool,
-) -> Result<()> {
+	max_retries: usize,
+	retry_base_delay_ms: u64,
+	fail_wait_ms: u64,
+}
+
+/// A single emoji that never came down successfully after every retry was
+/// exhausted, recorded so the run's end summary can point at exactly what
+/// needs another look instead of just a failure count.
+#[derive(Serialize)]
+struct Failure {
+	name: String,
+	url: String,
+	error: String,
+}
+
+async fn download_emoji(ctx: &DownloadContext, name: &str, url: &str) -> Result<DownloadOutcome> {
 	if !url.starts_with("http://") && !url.starts_with("https://") {
 		warn!("Skipped {} (invalid URL: {})", name, url);
-		return Ok (());
+		return Ok(DownloadOutcome::Skipped);
 	}
 
-	let sanitised_name = sanitise_filename(&name);
+	let sanitised_name = sanitise_filename(name);
 	let sanitised_name = if sanitised_name.is_empty() {
 		"emoji".to_string()
 	} else {
 		sanitised_name
 	};
 
-	let extension = extract_extension(&url);
-	let filename = format!("{}{}", sanitised_name, extension);
-	let filepath = output_dir.join(filename);
+	let url_extension = extract_extension(url);
 
-	if !skip_existence_check && filepath.exists() {
+	// The real extension may only be known after the response headers arrive
+	// (see extension_from_content_type below), so a pre-flight check can only
+	// ever match on the name stem, not the full filename.
+	if !ctx.skip_existence_check && ctx.sink.exists(&sanitised_name).await {
 		info!("Skipped {} (already exists)", name);
-		return Ok(());
+		return Ok(DownloadOutcome::Skipped);
 	}
 
-	let response = client
-		.get(&url)
+	let response = ctx.client
+		.get(url)
 		.timeout(std::time::Duration::from_secs(10))
 		.send()
 		.await
@@ -93,64 +290,69 @@ async fn download_emoji(
 		))
 	}
 
-	let bytes = response
-		.bytes()
-		.await
-		.context("Failed to read response body")?;
-
-	let mut file = fs::File::create(&filepath)
-		.await
-		.context(format!("Failed to create file {}", filepath.display()))?;
+	let extension = match url_extension {
+		Some(ext) => ext,
+		None => response
+			.headers()
+			.get(reqwest::header::CONTENT_TYPE)
+			.and_then(|v| v.to_str().ok())
+			.and_then(extension_from_content_type)
+			.unwrap_or_else(|| ".png".to_string()),
+	};
 
-	file.write_all(&bytes)
-		.await
-		.context(format!("Failed to write data to {}", filepath.display()))?;
+	let location = ctx.sink.write_emoji(&sanitised_name, &extension, Box::pin(response.bytes_stream())).await?;
 
-	file.flush()
-		.await
-		.context(format!("Failed to flush data to {}", filepath.display()))?;
-	let current = completed.fetch_add(1, Ordering::Relaxed) + 1;
-	info!("Downloaded {} -> {} [{}/{}]", name, filepath.display(), current, total);
-	Ok(())
+	let current = ctx.completed.fetch_add(1, Ordering::Relaxed) + 1;
+	info!("Downloaded {} -> {} [{}/{}]", name, location, current, ctx.total);
+	Ok(DownloadOutcome::Downloaded(location))
 }
 
-async fn download_emoji_with_retry(
-	client: &Client,
-	name: String,
-	url: String,
-	output_dir: &Path,
-	completed: Arc<AtomicUsize>,
-	total: usize,
-	skip_existence_check: bool,
-) -> Result<()> {
-	const MAX_RETRIES: usize = 3;
+/// What became of a single `download_emoji` call, so the retry wrapper knows
+/// whether to record a `done` or `skipped` manifest entry.
+enum DownloadOutcome {
+	Downloaded(String),
+	Skipped,
+}
 
+async fn download_emoji_with_retry(ctx: &DownloadContext, name: String, url: String) -> Result<(), Failure> {
 	if !url.starts_with("http://") && !url.starts_with("https://") {
 		warn!("Skipped {} (invalid URL: {})", name, url);
+		ctx.manifest.record(&name, &url, None, EmojiStatus::Skipped, 0);
 		return Ok(());
 	}
 
 	let mut last_error = None;
 
-	for attempt in 1..=MAX_RETRIES {
-		match download_emoji(client, name.clone(), url.clone(), output_dir.clone(), completed.clone(), total, skip_existence_check).await {
-			Ok(()) => return Ok(()),
+	for attempt in 1..=ctx.max_retries {
+		match download_emoji(ctx, &name, &url).await {
+			Ok(DownloadOutcome::Downloaded(location)) => {
+				ctx.manifest.record(&name, &url, Some(location), EmojiStatus::Done, (attempt - 1) as u32);
+				return Ok(());
+			}
+			Ok(DownloadOutcome::Skipped) => {
+				ctx.manifest.record(&name, &url, None, EmojiStatus::Skipped, (attempt - 1) as u32);
+				return Ok(());
+			}
 			Err(e) => {
-				if attempt < MAX_RETRIES {
-					let backoff = std::time::Duration::from_millis(500 * 2u64.pow((attempt - 1) as u32));
+				if attempt < ctx.max_retries {
+					let backoff_shift = 1u64.checked_shl((attempt - 1) as u32).unwrap_or(u64::MAX);
+					let backoff_ms = ctx.retry_base_delay_ms.saturating_mul(backoff_shift).min(ctx.fail_wait_ms);
+					let backoff = std::time::Duration::from_millis(backoff_ms);
 					warn!("Retry {}/{} for {}: {} (waiting {:?})",
-						attempt, MAX_RETRIES, name, e, backoff);
+						attempt, ctx.max_retries, name, e, backoff);
 					tokio::time::sleep(backoff).await;
 					last_error = Some(e);
 				} else {
-					return Err(e);
+					ctx.manifest.record(&name, &url, None, EmojiStatus::Failed, (attempt - 1) as u32);
+					return Err(Failure { name, url, error: e.to_string() });
 				}
 			}
 		}
 	}
 
 	// This should never be reached >:(
-	Err(last_error.unwrap_or_else(|| anyhow::anyhow!("Unknown error during retry")))
+	let error = last_error.map(|e| e.to_string()).unwrap_or_else(|| "Unknown error during retry".to_string());
+	Err(Failure { name, url, error })
 }
 
 #[tokio::main]
@@ -171,26 +373,39 @@ async fn main() -> Result<()> {
 	info!("Batch size: {}", args.batch_size);
 	info!("API URL: {}", args.api_url);
 
-	let existing_files = if args.skip_existence_check {
+	let sink: Arc<dyn EmojiSink> = match args.format {
+		OutputFormat::Dir => Arc::new(DirSink::new(args.output_dir.clone())),
+		OutputFormat::Zip => {
+			let archive_path = args.output_dir.join("emojis.zip");
+			info!("Writing emojis into archive: {}", archive_path.display());
+			Arc::new(ZipSink::new(&archive_path)?)
+		}
+	};
+
+	let existing_stems = if args.skip_existence_check {
 		HashSet::new()
 	} else {
-		info!("Scanning output directory for existing files...");
-		let mut files = HashSet::new();
-		let mut entries = fs::read_dir(&args.output_dir).await?;
-		while let Some(entry) = entries.next_entry().await? {
-			if let Ok(file_name) = entry.file_name().into_string() {
-				files.insert(file_name);
-			}
-		}
-		info!("Found {} existing files to skip", files.len());
-		files
+		info!("Scanning for existing emojis...");
+		let stems = sink.existing_names().await;
+		info!("Found {} existing emojis to skip", stems.len());
+		stems
 	};
 
+	let manifest = Arc::new(Manifest::load(&args.output_dir).await.context("Failed to load manifest")?);
+	if !sink.supports_resume() {
+		// emojis.zip is truncated fresh by ZipSink::new above, so a manifest
+		// from a previous run no longer describes what's on disk; consulting
+		// it here would "skip" emoji into an archive that never got them.
+		info!("--format zip always starts a fresh archive; ignoring manifest.json for resume decisions");
+	}
+
 	let client = ClientBuilder::new()
 		.pool_max_idle_per_host(args.concurrent)
 		.pool_idle_timeout(std::time::Duration::from_secs(30))
 		.timeout(std::time::Duration::from_secs(15))
 		.tcp_keepalive(std::time::Duration::from_secs(60))
+		.default_headers(build_headers(&args)?)
+		.redirect(same_host_redirect_policy(args.max_redirects))
 		.build()
 		.context("Failed to create HTTP client")?;
 
@@ -209,15 +424,50 @@ async fn main() -> Result<()> {
 
 	info!("Found {} emojis", emoji_data.len());
 
-	let valid_emojis: Vec<(String, String)> = emoji_data
+	let named_entries: HashMap<String, String> = emoji_data
 		.into_iter()
-		.filter_map(|(name, url)| {
-			url.as_str()
+		.filter_map(|(name, value)| {
+			value.as_str()
 				.filter(|s| !s.is_empty())
 				.map(|s| (name, s.to_string()))
 		})
 		.collect();
 
+	let (url_entries, alias_entries): (HashMap<String, String>, HashMap<String, String>) = named_entries
+		.into_iter()
+		.partition(|(_, value)| !value.starts_with(ALIAS_PREFIX));
+	let alias_entries: HashMap<String, String> = alias_entries
+		.into_iter()
+		.map(|(name, value)| (name, value.trim_start_matches(ALIAS_PREFIX).to_string()))
+		.collect();
+
+	info!("Found {} direct emojis and {} aliases", url_entries.len(), alias_entries.len());
+
+	let mut valid_emojis: Vec<(String, String)> = url_entries.iter().map(|(name, url)| (name.clone(), url.clone())).collect();
+
+	// With --link-aliases (directory output only), resolved aliases are linked
+	// (symlinked or copied) to their target's file after the main batch runs;
+	// otherwise they're just downloaded under their own name like any other emoji.
+	let mut linked_aliases: Vec<(String, String)> = Vec::new();
+
+	if args.link_aliases && args.format != OutputFormat::Dir {
+		warn!("--link-aliases has no effect with --format zip, downloading aliases directly instead");
+	}
+	let link_aliases = args.link_aliases && args.format == OutputFormat::Dir;
+
+	for alias_name in alias_entries.keys() {
+		match resolve_alias(alias_name, &url_entries, &alias_entries) {
+			Some((target_name, target_url)) => {
+				if link_aliases {
+					linked_aliases.push((alias_name.clone(), target_name.to_string()));
+				} else {
+					valid_emojis.push((alias_name.clone(), target_url.to_string()));
+				}
+			}
+			None => warn!("Could not resolve alias {}, skipping", alias_name),
+		}
+	}
+
 	let total_emojis = valid_emojis.len();
 	info!("Starting download of {} emojis in batches of {}...", total_emojis, args.batch_size);
 
@@ -225,11 +475,33 @@ async fn main() -> Result<()> {
 	let mut total_processed = 0;
 	let mut success_count = 0;
 
+	let progress = ProgressBar::new(total_emojis as u64);
+	progress.set_style(
+		ProgressStyle::with_template("{bar:40.cyan/blue} {pos}/{len} ({eta}) {msg}")
+			.unwrap_or_else(|_| ProgressStyle::default_bar()),
+	);
+
+	// skip_existence_check is always true here: the batch loop below already
+	// filtered against existing_stems/the manifest before calling through.
+	let ctx = DownloadContext {
+		client: client.clone(),
+		sink: sink.clone(),
+		manifest: manifest.clone(),
+		completed: completed.clone(),
+		total: total_emojis,
+		skip_existence_check: true,
+		max_retries: args.max_retries.max(1),
+		retry_base_delay_ms: args.retry_base_delay_ms,
+		fail_wait_ms: args.fail_wait_ms,
+	};
+
+	let mut failures: Vec<Failure> = Vec::new();
+
 	for(batch_index, batch) in valid_emojis.chunks(args.batch_size).enumerate() {
 		info!(
 			"Processing batch {}/{} ({} emojis)",
 			batch_index + 1,
-			(total_emojis + args.batch_size - 1) / args.batch_size,
+			total_emojis.div_ceil(args.batch_size),
 			batch.len()
 		);
 
@@ -237,10 +509,8 @@ async fn main() -> Result<()> {
 
 		let mut results = stream::iter(batch.to_vec())
 			.map(|(name, url)| {
-				let client = client.clone();
-				let output_dir = args.output_dir.clone();
-				let completed = completed.clone();
-				let existing_files = &existing_files;
+				let ctx = ctx.clone();
+				let existing_stems = &existing_stems;
 
 				async move {
 					if !args.skip_existence_check {
@@ -251,20 +521,18 @@ async fn main() -> Result<()> {
 							sanitised_name
 						};
 
-						let extension = extract_extension(&url);
-						let filename = format!("{}{}", sanitised_name, extension);
-
-						if existing_files.contains(&filename) {
-							completed.fetch_add(1, Ordering::Relaxed);
+						let manifest_done = ctx.sink.supports_resume() && ctx.manifest.is_done(&name);
+						if existing_stems.contains(&sanitised_name) || manifest_done {
+							ctx.completed.fetch_add(1, Ordering::Relaxed);
 							return Ok(());
 						}
 					}
 
-					match download_emoji_with_retry(&client, name.clone(), url, &output_dir, completed, total_emojis, true).await {
+					match download_emoji_with_retry(&ctx, name.clone(), url).await {
 						Ok(()) => Ok(()),
-						Err(e) => {
-							error!("Failed to download {}: {}", name, e);
-							Err(e)
+						Err(failure) => {
+							error!("Failed to download {}: {}", failure.name, failure.error);
+							Err(failure)
 						}
 					}
 				}
@@ -273,24 +541,60 @@ async fn main() -> Result<()> {
 
 		while let Some(result) = results.next().await {
 			total_processed += 1;
-			if result.is_ok() {
-				success_count += 1;
+			match result {
+				Ok(()) => success_count += 1,
+				Err(failure) => failures.push(failure),
 			}
+			progress.set_position(completed.load(Ordering::Relaxed) as u64);
 		}
 
 		let batch_elapsed = batch_start.elapsed();
 		info!(
 			"Batch {}/{} completed in {:.2?} ({} emojis/sec)",
 			batch_index + 1,
-			(total_emojis + args.batch_size - 1) / args.batch_size,
+			total_emojis.div_ceil(args.batch_size),
 			batch_elapsed,
 			batch.len() as f64 / batch_elapsed.as_secs_f64()
 		);
 
+		manifest.save().await.context("Failed to save manifest")?;
+
 		drop(results);
 		tokio::task::yield_now().await;
 	}
 
+	progress.finish_with_message("done");
+
+	if !linked_aliases.is_empty() {
+		info!("Linking {} aliases to their downloaded targets...", linked_aliases.len());
+		for (alias_name, target_name) in &linked_aliases {
+			if let Err(e) = link_alias(&args.output_dir, alias_name, target_name).await {
+				error!("Failed to link alias {} -> {}: {}", alias_name, target_name, e);
+			}
+		}
+	}
+
+	sink.finish().await.context("Failed to finalise output")?;
+	manifest.save().await.context("Failed to save manifest")?;
+
+	if !failures.is_empty() {
+		warn!("{} emoji failed after {} attempts each, see failures.txt/failures.json", failures.len(), args.max_retries.max(1));
+
+		let txt_report = failures
+			.iter()
+			.map(|f| format!("{} <{}>: {}", f.name, f.url, f.error))
+			.collect::<Vec<_>>()
+			.join("\n");
+		fs::write(args.output_dir.join("failures.txt"), txt_report)
+			.await
+			.context("Failed to write failures.txt")?;
+
+		let json_report = serde_json::to_string_pretty(&failures).context("Failed to serialise failures")?;
+		fs::write(args.output_dir.join("failures.json"), json_report)
+			.await
+			.context("Failed to write failures.json")?;
+	}
+
 	let elapsed = start_time.elapsed();
 	info!(
 		"Download complete: {} / {} successful in {:.2?} ({} emojis/sec)",
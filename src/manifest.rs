@@ -0,0 +1,77 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tokio::fs;
+
+pub const MANIFEST_FILENAME: &str = "manifest.json";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmojiStatus {
+	Done,
+	Failed,
+	Skipped,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ManifestEntry {
+	pub url: String,
+	pub filename: Option<String>,
+	pub status: EmojiStatus,
+	pub retries: u32,
+}
+
+/// Tracks the outcome of every emoji processed so far, persisted to
+/// `manifest.json` in the output directory. Reloading it at startup lets a
+/// run skip emoji already marked `done` and retry only what previously
+/// failed, instead of relying solely on the filename-existence scan.
+pub struct Manifest {
+	path: PathBuf,
+	entries: Mutex<HashMap<String, ManifestEntry>>,
+}
+
+impl Manifest {
+	pub async fn load(output_dir: &Path) -> Result<Self> {
+		let path = output_dir.join(MANIFEST_FILENAME);
+
+		let entries = match fs::read_to_string(&path).await {
+			Ok(contents) => serde_json::from_str(&contents).context(format!("Failed to parse {}", path.display()))?,
+			Err(_) => HashMap::new(),
+		};
+
+		Ok(Self {
+			path,
+			entries: Mutex::new(entries),
+		})
+	}
+
+	pub fn is_done(&self, name: &str) -> bool {
+		matches!(
+			self.entries.lock().expect("manifest mutex poisoned").get(name),
+			Some(entry) if entry.status == EmojiStatus::Done
+		)
+	}
+
+	pub fn record(&self, name: &str, url: &str, filename: Option<String>, status: EmojiStatus, retries: u32) {
+		self.entries.lock().expect("manifest mutex poisoned").insert(
+			name.to_string(),
+			ManifestEntry {
+				url: url.to_string(),
+				filename,
+				status,
+				retries,
+			},
+		);
+	}
+
+	pub async fn save(&self) -> Result<()> {
+		let snapshot = self.entries.lock().expect("manifest mutex poisoned").clone();
+		let json = serde_json::to_string_pretty(&snapshot).context("Failed to serialise manifest")?;
+		fs::write(&self.path, json)
+			.await
+			.context(format!("Failed to write {}", self.path.display()))?;
+		Ok(())
+	}
+}
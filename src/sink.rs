@@ -0,0 +1,207 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::Stream;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Mutex;
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+use tokio_stream::StreamExt;
+
+pub type ByteStream = Pin<Box<dyn Stream<Item = reqwest::Result<Bytes>> + Send>>;
+
+/// Destination an emoji's bytes are written to. Lets the downloader target a
+/// plain directory or a single archive without knowing which it's writing
+/// into, so the concurrency/retry logic in `main` stays the same either way.
+#[async_trait]
+pub trait EmojiSink: Send + Sync {
+	/// Writes `name` + `extension`'s bytes from `stream`, returning a string
+	/// describing where they landed (for logging).
+	async fn write_emoji(&self, name: &str, extension: &str, stream: ByteStream) -> Result<String>;
+
+	/// Returns true if an emoji called `name` (any extension) has already
+	/// been written to this sink.
+	async fn exists(&self, name: &str) -> bool;
+
+	/// Returns the names of every emoji already present in this sink, so the
+	/// caller can filter a whole batch in one pass instead of calling
+	/// `exists` per emoji. Empty when the sink has no notion of resuming
+	/// (e.g. `ZipSink`, which always starts a fresh archive).
+	async fn existing_names(&self) -> HashSet<String> {
+		HashSet::new()
+	}
+
+	/// Finalises the sink once every emoji has been processed. A no-op for
+	/// `DirSink`; closes out the central directory for `ZipSink`.
+	async fn finish(&self) -> Result<()> {
+		Ok(())
+	}
+
+	/// Whether a previous run's output can be resumed from. `DirSink` can,
+	/// since completed files stay on disk between runs; `ZipSink` can't,
+	/// since it truncates `emojis.zip` on every `new()`, so honouring a stale
+	/// manifest would "skip" emoji that no longer exist anywhere and leave
+	/// the fresh archive missing entries.
+	fn supports_resume(&self) -> bool {
+		true
+	}
+}
+
+/// Writes each emoji to its own file under `output_dir`, the original
+/// behaviour. Files are written via temp-file-and-rename so a crash never
+/// leaves a truncated file at the real name.
+pub struct DirSink {
+	output_dir: PathBuf,
+}
+
+impl DirSink {
+	pub fn new(output_dir: PathBuf) -> Self {
+		Self { output_dir }
+	}
+}
+
+#[async_trait]
+impl EmojiSink for DirSink {
+	async fn write_emoji(&self, name: &str, extension: &str, mut stream: ByteStream) -> Result<String> {
+		let filepath = self.output_dir.join(format!("{}{}", name, extension));
+		let temp_filepath = filepath.with_extension("tmp");
+
+		let write_result: Result<()> = async {
+			let mut file = fs::File::create(&temp_filepath)
+				.await
+				.context(format!("Failed to create file {}", temp_filepath.display()))?;
+
+			while let Some(chunk) = stream.next().await {
+				let chunk = chunk.context("Failed to read response chunk")?;
+				file.write_all(&chunk)
+					.await
+					.context(format!("Failed to write data to {}", temp_filepath.display()))?;
+			}
+
+			file.flush()
+				.await
+				.context(format!("Failed to flush data to {}", temp_filepath.display()))?;
+
+			Ok(())
+		}.await;
+
+		if let Err(e) = write_result {
+			let _ = fs::remove_file(&temp_filepath).await;
+			return Err(e);
+		}
+
+		fs::rename(&temp_filepath, &filepath)
+			.await
+			.context(format!("Failed to move {} into place at {}", temp_filepath.display(), filepath.display()))?;
+
+		Ok(filepath.display().to_string())
+	}
+
+	async fn exists(&self, name: &str) -> bool {
+		find_by_stem(&self.output_dir, name).await.is_some()
+	}
+
+	async fn existing_names(&self) -> HashSet<String> {
+		let mut names = HashSet::new();
+		let mut entries = match fs::read_dir(&self.output_dir).await {
+			Ok(entries) => entries,
+			Err(_) => return names,
+		};
+
+		while let Ok(Some(entry)) = entries.next_entry().await {
+			let path = entry.path();
+			if path.extension().and_then(|e| e.to_str()) == Some("tmp") {
+				// Leftover from an interrupted write; the real file was never
+				// completed, so don't let its stem mask a retry.
+				continue;
+			}
+			if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+				names.insert(stem.to_string());
+			}
+		}
+
+		names
+	}
+}
+
+/// Finds the file in `dir` whose stem (filename minus extension) matches
+/// `stem`, if any. Ignores `.tmp` files, since those are leftovers from an
+/// interrupted write rather than a completed download.
+pub async fn find_by_stem(dir: &Path, stem: &str) -> Option<PathBuf> {
+	let mut entries = fs::read_dir(dir).await.ok()?;
+
+	while let Ok(Some(entry)) = entries.next_entry().await {
+		let path = entry.path();
+		if path.extension().and_then(|e| e.to_str()) == Some("tmp") {
+			continue;
+		}
+		if path.file_stem().and_then(|s| s.to_str()) == Some(stem) {
+			return Some(path);
+		}
+	}
+
+	None
+}
+
+/// Streams every emoji into a single `.zip` archive instead of the output
+/// directory, so the whole set can be shared as one file. A `zip::ZipWriter`
+/// only ever writes one entry at a time, so concurrent downloads serialise
+/// on the inner mutex here rather than on disk I/O; each emoji is still
+/// buffered fully before it's appended, so the mutex is only taken for the
+/// synchronous write and never held across an `.await`.
+pub struct ZipSink {
+	writer: Mutex<zip::ZipWriter<std::fs::File>>,
+}
+
+impl ZipSink {
+	pub fn new(archive_path: &Path) -> Result<Self> {
+		let file = std::fs::File::create(archive_path)
+			.context(format!("Failed to create archive {}", archive_path.display()))?;
+
+		Ok(Self {
+			writer: Mutex::new(zip::ZipWriter::new(file)),
+		})
+	}
+}
+
+#[async_trait]
+impl EmojiSink for ZipSink {
+	async fn write_emoji(&self, name: &str, extension: &str, mut stream: ByteStream) -> Result<String> {
+		let filename = format!("{}{}", name, extension);
+
+		let mut buf = Vec::new();
+		while let Some(chunk) = stream.next().await {
+			let chunk = chunk.context("Failed to read response chunk")?;
+			buf.extend_from_slice(&chunk);
+		}
+
+		let mut writer = self.writer.lock().expect("zip writer mutex poisoned");
+		writer
+			.start_file(&filename, zip::write::FileOptions::default())
+			.context(format!("Failed to start zip entry {}", filename))?;
+		std::io::Write::write_all(&mut *writer, &buf).context(format!("Failed to write zip entry {}", filename))?;
+
+		Ok(filename)
+	}
+
+	async fn exists(&self, _name: &str) -> bool {
+		// A zip archive is always written fresh; there's nothing on disk yet
+		// to resume from, unlike DirSink's output directory.
+		false
+	}
+
+	fn supports_resume(&self) -> bool {
+		false
+	}
+
+	async fn finish(&self) -> Result<()> {
+		self.writer
+			.lock()
+			.expect("zip writer mutex poisoned")
+			.finish()
+			.context("Failed to finalise zip archive")?;
+		Ok(())
+	}
+}